@@ -1,7 +1,19 @@
 use std::slice::{from_raw_parts, from_raw_parts_mut};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
 use super::super::bindings::{ws2811_fini, ws2811_render, ws2811_t, ws2811_wait};
-use super::super::util::{RawColor, Result};
+use super::super::pixels::Pixels;
+use super::super::util::{RawColor, Result, WS2811Error};
+
+/// Shared state for `wait_timeout`: whether a background wait is currently
+/// in flight on the device, and the result of the most recently completed
+/// one.
+#[derive(Debug, Default)]
+struct WaitState {
+    in_flight: bool,
+    result: Option<Result<()>>,
+}
 
 /// The main struct used to control lights.  Provides ways of
 /// accessing the light color values and rendering those values to
@@ -9,6 +21,8 @@ use super::super::util::{RawColor, Result};
 #[derive(Clone, Debug)]
 pub struct Controller {
     c_struct: Arc<Mutex<ws2811_t>>,
+    #[allow(clippy::type_complexity)]
+    wait_state: Arc<(Mutex<WaitState>, Condvar)>,
 }
 
 impl Controller {
@@ -16,7 +30,45 @@ impl Controller {
     ///
     /// Note: This is only to be called from the Builder struct
     pub fn new(c_struct: ws2811_t) -> Self {
-        Controller { c_struct: Arc::new(Mutex::new(c_struct)) }
+        Controller {
+            c_struct: Arc::new(Mutex::new(c_struct)),
+            wait_state: Arc::new((Mutex::new(WaitState::default()), Condvar::new())),
+        }
+    }
+
+    /// Locks `c_struct`, recovering from a poisoned lock instead of
+    /// panicking.
+    ///
+    /// A panic while some thread held this lock (e.g. partway through a
+    /// `render`) would otherwise leave every future call on this
+    /// `Controller` (and its clones) panicking in turn. The `ws2811_t` a
+    /// poisoned guard protects is still a perfectly valid C struct, so it's
+    /// safe to recover it with `into_inner()` and keep going. We also clear
+    /// the `Mutex`'s poison flag while we're at it: `into_inner()` alone
+    /// doesn't clear it, so without this a single panic would leave every
+    /// later call observing `Err` forever, trading the original panic for a
+    /// permanent `LockPoisoned` error instead of actually recovering. The
+    /// returned `bool` tells the caller whether the lock was found
+    /// poisoned, so call sites that can report an error get the chance to
+    /// do so.
+    ///
+    /// This takes `c_struct` explicitly, rather than `&self`, so the
+    /// background thread spawned by `wait_timeout` can share this recovery
+    /// logic without needing a full `Controller` of its own (and the
+    /// `ws2811_fini` call that dropping one would trigger).
+    fn lock_c_struct(c_struct: &Arc<Mutex<ws2811_t>>) -> (MutexGuard<'_, ws2811_t>, bool) {
+        match c_struct.lock() {
+            Ok(guard) => (guard, false),
+            Err(poisoned) => {
+                let guard = poisoned.into_inner();
+                c_struct.clear_poison();
+                (guard, true)
+            }
+        }
+    }
+
+    fn lock(&self) -> (MutexGuard<'_, ws2811_t>, bool) {
+        Self::lock_c_struct(&self.c_struct)
     }
 
     /// Render the colors to the string.
@@ -25,37 +77,85 @@ impl Controller {
     /// is a somewhat costly operation that should
     /// be batched.
     pub fn render(&mut self) -> Result<()> {
-        let mut lock = self.c_struct.lock().unwrap();
-        unsafe {
-            let result: Result<()> = ws2811_render(&mut *lock).into();
-            match result {
-                Ok(_) => Ok(()),
-                Err(e) => return Err(e),
-            }
-        }
-        /*
-        unsafe {
-            return ws2811_render(&mut self.c_struct).into();
+        let (mut lock, poisoned) = self.lock();
+        if poisoned {
+            return Err(WS2811Error::LockPoisoned);
         }
-        */
+        unsafe { ws2811_render(&mut *lock).into() }
     }
 
     /// Wait for a render to be completed.
     pub fn wait(&mut self) -> Result<()> {
-        let mut lock = self.c_struct.lock().unwrap();
-        unsafe {
-            let result: Result<()> = ws2811_wait(&mut *lock).into();
-            match result {
-                Ok(_) => Ok(()),
-                Err(e) => return Err(e),
-            }
+        let (mut lock, poisoned) = self.lock();
+        if poisoned {
+            return Err(WS2811Error::LockPoisoned);
         }
+        unsafe { ws2811_wait(&mut *lock).into() }
+    }
 
-        /*
-        unsafe {
-            return ws2811_wait(&mut self.c_struct).into();
+    /// Wait for a render to be completed, but give up after `timeout`.
+    ///
+    /// Returns `Ok(true)` if the render finished before the deadline and
+    /// `Ok(false)` if it didn't, surfacing the underlying `wait()` error
+    /// (including a poisoned lock) if that's what it ran into instead.
+    /// This exists because the underlying `ws2811_wait` has no timeout of
+    /// its own, so a hung DMA transfer would otherwise stall the calling
+    /// thread forever; this lets animation loops enforce a frame budget and
+    /// bail out instead.
+    ///
+    /// The actual wait runs on a single background thread shared by every
+    /// clone of this `Controller`: if one is already in flight (e.g. from
+    /// an earlier `wait_timeout` call that itself timed out), this call
+    /// just waits on its result instead of spawning another. Spawning one
+    /// thread per call would otherwise let them pile up unboundedly against
+    /// the very same hung `ws2811_wait` this method exists to route around.
+    /// That shared thread only ever touches `c_struct`, not a full
+    /// `Controller`, so no extra `Drop::drop` (and its `ws2811_fini` call)
+    /// fires when it finishes.
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        let (state_lock, condvar) = &*self.wait_state;
+        let mut state = state_lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        if !state.in_flight {
+            state.in_flight = true;
+            state.result = None;
+
+            let c_struct = Arc::clone(&self.c_struct);
+            let wait_state = Arc::clone(&self.wait_state);
+            thread::spawn(move || {
+                let (mut lock, poisoned) = Self::lock_c_struct(&c_struct);
+                let result = if poisoned {
+                    Err(WS2811Error::LockPoisoned)
+                } else {
+                    unsafe { ws2811_wait(&mut *lock).into() }
+                };
+                drop(lock);
+
+                let (state_lock, condvar) = &*wait_state;
+                let mut state = state_lock.lock().unwrap_or_else(|e| e.into_inner());
+                state.in_flight = false;
+                state.result = Some(result);
+                condvar.notify_all();
+            });
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(result) = state.result {
+                return result.map(|_| true);
+            }
+            let remaining = match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) => remaining,
+                None => return Ok(false),
+            };
+            let (new_state, wait_result) = condvar
+                .wait_timeout(state, remaining)
+                .unwrap_or_else(|e| e.into_inner());
+            state = new_state;
+            if wait_result.timed_out() && state.result.is_none() {
+                return Ok(false);
+            }
         }
-        */
     }
 
     /// Gets the channels with non-zero number of LED's associated with them.
@@ -63,26 +163,21 @@ impl Controller {
     /// I know this is somewhat non-intuitive, but naming it something like
     /// `active_channels(&self)` seemed overly verbose.
     pub fn channels(&self) -> Vec<usize> {
-        let lock = self.c_struct.lock().unwrap();
+        let (lock, _) = self.lock();
         (0..lock.channel.len())
             .filter(|&x| lock.channel[x].count > 0)
             .collect::<Vec<_>>()
-        /*
-        (0..self.c_struct.channel.len())
-            .filter(|x: _| self.c_struct.channel[x.clone()].count > 0)
-            .collect::<Vec<_>>()
-        */
     }
 
     /// Gets the brightness of the LEDs
     pub fn brightness(&self, channel: usize) -> u8 {
-        let lock = self.c_struct.lock().unwrap();
+        let (lock, _) = self.lock();
         lock.channel[channel].brightness
     }
 
     /// Sets the brighness of the LEDs
     pub fn set_brightness(&mut self, channel: usize, value: u8) {
-        let mut lock = self.c_struct.lock().unwrap();
+        let (mut lock, _) = self.lock();
         lock.channel[channel].brightness = value;
     }
 
@@ -100,7 +195,7 @@ impl Controller {
          * which is safe as long as our friends in "C land" hold to their
          * memory layout and we use a data type with compatible layout.
          */
-        let lock = self.c_struct.lock().unwrap();
+        let (lock, _) = self.lock();
         unsafe {
             from_raw_parts(
                 lock.channel[channel].leds as *const RawColor,
@@ -123,7 +218,7 @@ impl Controller {
          * which is safe as long as our friends in "C land" hold to their
          * memory layout and we use a data type with compatible layout.
          */
-        let lock = self.c_struct.lock().unwrap();
+        let (lock, _) = self.lock();
         unsafe {
             from_raw_parts_mut(
                 lock.channel[channel].leds as *mut RawColor,
@@ -131,6 +226,25 @@ impl Controller {
             )
         }
     }
+
+    /// Gets a safe, typed view over a channel's pixels.
+    ///
+    /// This is the recommended way to read and write colors: unlike
+    /// `leds_mut`, it translates `Color` to and from whatever byte order
+    /// the channel's strip type uses internally, so callers never reorder
+    /// bytes by hand. `leds_mut` remains available for advanced or
+    /// zero-cost use.
+    pub fn pixels_mut(&mut self, channel: usize) -> Pixels<'_> {
+        let (lock, _) = self.lock();
+        let strip_type = lock.channel[channel].strip_type;
+        let leds = unsafe {
+            from_raw_parts_mut(
+                lock.channel[channel].leds as *mut RawColor,
+                lock.channel[channel].count as usize,
+            )
+        };
+        Pixels::new(leds, strip_type)
+    }
 }
 
 impl Drop for Controller {
@@ -140,7 +254,7 @@ impl Drop for Controller {
          * function during the drop process.  Unfortunately,
          * I don't have a better way of dealing with this.
          */
-        let mut lock = self.c_struct.lock().unwrap();
+        let (mut lock, _) = self.lock();
         unsafe {
             ws2811_fini(&mut *lock);
         }
@@ -0,0 +1,11 @@
+//! A safe Rust wrapper around the `rpi_ws281x` C library for driving
+//! WS281x/SK6812 LED strings from a Raspberry Pi.
+
+mod bindings;
+mod controller;
+mod pixels;
+mod util;
+
+pub use controller::Controller;
+pub use pixels::{Color, Pixels};
+pub use util::{RawColor, Result, WS2811Error};
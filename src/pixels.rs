@@ -0,0 +1,200 @@
+use super::util::RawColor;
+
+/// An RGB(W) color, independent of how a particular strip wants its bytes
+/// ordered on the wire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub w: u8,
+}
+
+impl Color {
+    pub fn new(r: u8, g: u8, b: u8, w: u8) -> Self {
+        Color { r, g, b, w }
+    }
+}
+
+/// The byte order a channel's strip type packs `Color` components into,
+/// decoded from the raw `strip_type` codes defined in `ws2811.h`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StripOrder {
+    Rgb,
+    Rbg,
+    Grb,
+    Gbr,
+    Brg,
+    Bgr,
+    Rgbw,
+    Rbgw,
+    Grbw,
+    Gbrw,
+    Brgw,
+    Bgrw,
+}
+
+impl StripOrder {
+    /// Decodes a channel's raw `strip_type` into the byte order it uses.
+    /// Falls back to GRB, the most common WS281x wire order, for anything
+    /// unrecognized rather than failing outright.
+    fn from_strip_type(strip_type: u32) -> Self {
+        match strip_type {
+            0x00100800 => StripOrder::Rgb,
+            0x00100008 => StripOrder::Rbg,
+            0x00081000 => StripOrder::Grb,
+            0x00080010 => StripOrder::Gbr,
+            0x00001008 => StripOrder::Brg,
+            0x00000810 => StripOrder::Bgr,
+            0x18100800 => StripOrder::Rgbw,
+            0x18100008 => StripOrder::Rbgw,
+            0x18081000 => StripOrder::Grbw,
+            0x18080010 => StripOrder::Gbrw,
+            0x18001008 => StripOrder::Brgw,
+            0x18000810 => StripOrder::Bgrw,
+            _ => StripOrder::Grb,
+        }
+    }
+
+    fn pack(self, color: Color) -> RawColor {
+        let Color { r, g, b, w } = color;
+        match self {
+            StripOrder::Rgb | StripOrder::Rgbw => [r, g, b, w],
+            StripOrder::Rbg | StripOrder::Rbgw => [r, b, g, w],
+            StripOrder::Grb | StripOrder::Grbw => [g, r, b, w],
+            StripOrder::Gbr | StripOrder::Gbrw => [g, b, r, w],
+            StripOrder::Brg | StripOrder::Brgw => [b, r, g, w],
+            StripOrder::Bgr | StripOrder::Bgrw => [b, g, r, w],
+        }
+    }
+
+    fn unpack(self, raw: RawColor) -> Color {
+        let [a, b2, c, d] = raw;
+        match self {
+            StripOrder::Rgb | StripOrder::Rgbw => Color::new(a, b2, c, d),
+            StripOrder::Rbg | StripOrder::Rbgw => Color::new(a, c, b2, d),
+            StripOrder::Grb | StripOrder::Grbw => Color::new(b2, a, c, d),
+            StripOrder::Gbr | StripOrder::Gbrw => Color::new(c, a, b2, d),
+            StripOrder::Brg | StripOrder::Brgw => Color::new(b2, c, a, d),
+            StripOrder::Bgr | StripOrder::Bgrw => Color::new(c, b2, a, d),
+        }
+    }
+}
+
+/// A safe, typed view over a channel's raw LED buffer.
+///
+/// `Controller::pixels_mut` is the recommended way to read and write pixel
+/// colors: it translates `Color` to and from whatever byte order the
+/// channel's strip type uses, so callers never have to reorder bytes by
+/// hand the way they would working with `leds_mut` directly. It borrows the
+/// `Controller` for its lifetime, so the usual aliasing guarantees still
+/// hold.
+pub struct Pixels<'a> {
+    leds: &'a mut [RawColor],
+    order: StripOrder,
+}
+
+impl<'a> Pixels<'a> {
+    pub(crate) fn new(leds: &'a mut [RawColor], strip_type: u32) -> Self {
+        Pixels { leds, order: StripOrder::from_strip_type(strip_type) }
+    }
+
+    /// The number of pixels in this view.
+    pub fn len(&self) -> usize {
+        self.leds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leds.is_empty()
+    }
+
+    /// Gets the color of the pixel at `index`.
+    pub fn get(&self, index: usize) -> Color {
+        self.order.unpack(self.leds[index])
+    }
+
+    /// Sets the color of the pixel at `index`.
+    pub fn set(&mut self, index: usize, color: Color) {
+        self.leds[index] = self.order.pack(color);
+    }
+
+    /// Sets every pixel in the view to `color`.
+    pub fn fill(&mut self, color: Color) {
+        let raw = self.order.pack(color);
+        for led in self.leds.iter_mut() {
+            *led = raw;
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Pixels<'a> {
+    type Item = Color;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter { leds: self.leds.iter(), order: self.order }
+    }
+}
+
+/// An iterator over the decoded `Color` of each pixel in a `Pixels` view.
+pub struct Iter<'a> {
+    leds: std::slice::Iter<'a, RawColor>,
+    order: StripOrder,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Color;
+
+    fn next(&mut self) -> Option<Color> {
+        self.leds.next().map(|&raw| self.order.unpack(raw))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_ORDERS: [StripOrder; 12] = [
+        StripOrder::Rgb,
+        StripOrder::Rbg,
+        StripOrder::Grb,
+        StripOrder::Gbr,
+        StripOrder::Brg,
+        StripOrder::Bgr,
+        StripOrder::Rgbw,
+        StripOrder::Rbgw,
+        StripOrder::Grbw,
+        StripOrder::Gbrw,
+        StripOrder::Brgw,
+        StripOrder::Bgrw,
+    ];
+
+    #[test]
+    fn pack_then_unpack_round_trips_for_every_order() {
+        let color = Color::new(0x11, 0x22, 0x33, 0x44);
+        for &order in &ALL_ORDERS {
+            let raw = order.pack(color);
+            assert_eq!(order.unpack(raw), color, "{:?} did not round-trip", order);
+        }
+    }
+
+    #[test]
+    fn grb_packs_and_unpacks_in_wire_order() {
+        let color = Color::new(1, 2, 3, 4);
+        assert_eq!(StripOrder::Grb.pack(color), [2, 1, 3, 4]);
+        assert_eq!(StripOrder::Grb.unpack([2, 1, 3, 4]), color);
+    }
+
+    #[test]
+    fn from_strip_type_decodes_known_codes() {
+        assert_eq!(StripOrder::from_strip_type(0x00100800), StripOrder::Rgb);
+        assert_eq!(StripOrder::from_strip_type(0x00081000), StripOrder::Grb);
+        assert_eq!(StripOrder::from_strip_type(0x18100800), StripOrder::Rgbw);
+        assert_eq!(StripOrder::from_strip_type(0x18081000), StripOrder::Grbw);
+    }
+
+    #[test]
+    fn from_strip_type_falls_back_to_grb_for_unknown_codes() {
+        assert_eq!(StripOrder::from_strip_type(0xdead_beef), StripOrder::Grb);
+    }
+}
@@ -0,0 +1,59 @@
+use std::os::raw::c_int;
+
+/// A single LED's raw color bytes, laid out exactly as the underlying C
+/// library expects them for the channel's configured strip type (e.g.
+/// GRB, RGBW) rather than a fixed `[r, g, b, w]` order.
+pub type RawColor = [u8; 4];
+
+/// The `Result` alias used throughout this crate.
+pub type Result<T> = std::result::Result<T, WS2811Error>;
+
+/// Errors that can occur while configuring or driving the LED string.
+///
+/// The `Hardware*` variants mirror the `ws2811_return_t` codes returned by
+/// `libws2811`; the rest are conditions detected on the Rust side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WS2811Error {
+    HardwareGeneric,
+    HardwareOutOfMemory,
+    HardwareNotSupported,
+    HardwareMemLock,
+    HardwareMmap,
+    HardwareMapRegisters,
+    HardwareGpioInit,
+    HardwarePwmSetup,
+    HardwareMailboxDevice,
+    HardwareDma,
+    HardwareIllegalGpio,
+    HardwarePcmSetup,
+    HardwareSpiSetup,
+    HardwareSpiTransfer,
+    HardwareUnknown(c_int),
+    /// A `Controller`'s internal lock was poisoned by a panic on another
+    /// thread while it was held. The underlying `ws2811_t` has already
+    /// been recovered and is safe to keep using.
+    LockPoisoned,
+}
+
+impl From<c_int> for Result<()> {
+    fn from(code: c_int) -> Self {
+        match code {
+            0 => Ok(()),
+            -1 => Err(WS2811Error::HardwareGeneric),
+            -2 => Err(WS2811Error::HardwareOutOfMemory),
+            -3 => Err(WS2811Error::HardwareNotSupported),
+            -4 => Err(WS2811Error::HardwareMemLock),
+            -5 => Err(WS2811Error::HardwareMmap),
+            -6 => Err(WS2811Error::HardwareMapRegisters),
+            -7 => Err(WS2811Error::HardwareGpioInit),
+            -8 => Err(WS2811Error::HardwarePwmSetup),
+            -9 => Err(WS2811Error::HardwareMailboxDevice),
+            -10 => Err(WS2811Error::HardwareDma),
+            -11 => Err(WS2811Error::HardwareIllegalGpio),
+            -12 => Err(WS2811Error::HardwarePcmSetup),
+            -13 => Err(WS2811Error::HardwareSpiSetup),
+            -14 => Err(WS2811Error::HardwareSpiTransfer),
+            other => Err(WS2811Error::HardwareUnknown(other)),
+        }
+    }
+}